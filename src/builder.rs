@@ -1,9 +1,13 @@
 use super::*;
 
 use geom::prelude::*;
-use geom::Position;
+use geom::{Position, Vec3};
+use nearest_kdtree::distance::squared_euclidean;
 use nearest_kdtree::KdTree;
+use rand::{thread_rng, Rng};
 use sampling::into_poisson_disk_set;
+use sdf::{surface_nets, thin_by_minimum_distance};
+use shape::{BoundarySample, FromPositionNormal};
 use surfel::Surfel;
 
 pub struct SurfaceBuilder<S: Position> {
@@ -18,12 +22,31 @@ pub enum SurfelSampling {
     /// Examines each triangle and randomly samples an amount of points proporitional to the given
     /// point density per square unit in world space. Clumps together on smaller scales, but crazy fast.
     /// Use `MinimumDistance` for better quality.
+    ///
+    /// Implemented as area-weighted Monte Carlo sampling: the expected number of samples for a
+    /// triangle is `area * density`, rounded down with the fractional remainder resolved by a
+    /// Bernoulli trial so the overall sample count stays unbiased, and each sample is placed by
+    /// drawing uniform barycentric coordinates reflected into the triangle.
     PerSqrUnit(f32),
     /// Uses [dart throwing](https://www.researchgate.net/publication/230312465_Dart_Throwing_on_Surfaces)
     /// on surfaces as proposed for David Cline et. al. to generate a poisson disk set with given minimum distance
     /// for points in the resulting set.
     /// The strategy is slower than `PerSqrUnit`, but surfels are more evenly spaced.
     MinimumDistance(f32),
+    /// Produces a near-Poisson-disk distribution much faster than `MinimumDistance` by following
+    /// Mitchell's best-candidate algorithm: the set is seeded with one area-weighted random
+    /// sample, then each further sample is the best of `candidates_per_sample` fresh
+    /// area-weighted candidates, where "best" means farthest from every sample picked so far.
+    /// Unlike `MinimumDistance`, this yields exactly `count` surfels; raise
+    /// `candidates_per_sample` to trade performance for more even spacing.
+    /// `candidates_per_sample` must be at least 1; a value of 0 yields no surfels at all.
+    /// If an `accept` predicate supplied to the sampling call rejects almost everything
+    /// drawn, selection gives up after a bounded number of rejected draws and yields
+    /// fewer than `count` surfels rather than looping forever.
+    BestCandidate {
+        count: usize,
+        candidates_per_sample: usize,
+    },
 }
 
 impl<V: Position, D> SurfaceBuilder<Surfel<V, D>> {
@@ -34,21 +57,407 @@ impl<V: Position, D> SurfaceBuilder<Surfel<V, D>> {
         self
     }
 
-    pub fn sample_triangles<I, T>(mut self, triangles: I, prototype_surfel_data: &D) -> Self
+    /// Samples the given triangles according to the configured `SurfelSampling` strategy.
+    ///
+    /// If `accept` is given, each candidate is still drawn and consumed from the sampling
+    /// stream, but discarded rather than added to the surface whenever `accept` returns
+    /// `false` for it. This lets callers carve out holes or restrict sampling to a region
+    /// (e.g. via a texture/UV lookup on the interpolated vertex) without pre-splitting the
+    /// mesh, while keeping the density/minimum-distance semantics of the chosen strategy
+    /// intact over the accepted region.
+    pub fn sample_triangles<I, T>(
+        mut self,
+        triangles: I,
+        prototype_surfel_data: &D,
+        accept: Option<&dyn Fn(&V) -> bool>,
+    ) -> Self
     where
         T: Clone + InterpolateVertex<Vertex = V> + FromVertices<Vertex = V>,
         V: Clone,
         I: IntoIterator<Item = T>,
         D: Clone,
     {
-        self.samples.extend(match self.sampling {
-            SurfelSampling::MinimumDistance(min_dist) => into_poisson_disk_set(triangles, min_dist)
-                .map(|v| Surfel::new(v, prototype_surfel_data.clone())),
-            _ => unimplemented!("Only SurfelSampling::MinimumDistance implemented at the moment"),
-        });
+        let raw: Vec<V> = match self.sampling {
+            SurfelSampling::MinimumDistance(min_dist) => {
+                into_poisson_disk_set(triangles, min_dist).collect()
+            }
+            SurfelSampling::PerSqrUnit(density) => sample_triangles_per_sqr_unit(triangles, density),
+            SurfelSampling::BestCandidate {
+                count,
+                candidates_per_sample,
+            } => sample_triangles_best_candidate(triangles, count, candidates_per_sample, accept),
+        };
+
+        self.extend_accepted(raw, accept, prototype_surfel_data);
+
+        self
+    }
+
+    /// Samples surfels directly on an analytic primitive such as `Sphere` or `Cuboid`,
+    /// without first tessellating it into a triangle mesh. Density and cardinality are
+    /// driven by the configured `SurfelSampling` exactly as for `sample_triangles`, and
+    /// `accept` carves out regions exactly as described there.
+    ///
+    /// There is no mesh to dart-throw over, so `SurfelSampling::MinimumDistance(min_dist)`
+    /// is approximated by picking a target count from the shape's area assuming circular
+    /// packing at `min_dist` spacing, then running the same best-candidate search as
+    /// `BestCandidate`.
+    pub fn sample_shape<B>(
+        mut self,
+        shape: &B,
+        prototype_surfel_data: &D,
+        accept: Option<&dyn Fn(&V) -> bool>,
+    ) -> Self
+    where
+        B: BoundarySample,
+        V: FromPositionNormal,
+        D: Clone,
+    {
+        let raw: Vec<V> = match self.sampling {
+            SurfelSampling::PerSqrUnit(density) => sample_shape_per_sqr_unit(shape, density),
+            SurfelSampling::BestCandidate {
+                count,
+                candidates_per_sample,
+            } => select_best_candidates(count, candidates_per_sample, accept, |rng| {
+                draw_shape_sample(shape, rng)
+            }),
+            SurfelSampling::MinimumDistance(min_dist) => {
+                let count = shape_best_candidate_count(shape, min_dist);
+                select_best_candidates(count, DEFAULT_CANDIDATES_PER_SAMPLE, accept, |rng| {
+                    draw_shape_sample(shape, rng)
+                })
+            }
+        };
+
+        self.extend_accepted(raw, accept, prototype_surfel_data);
+
+        self
+    }
+
+    /// Builds surfels from the zero isosurface of a signed distance field `sdf`, extracted
+    /// over a regular grid spanning `bounds_min`..`bounds_max` with `resolution` cells per
+    /// axis using Naive Surface Nets. One surfel is emitted per dual vertex; if the sampling
+    /// strategy is `SurfelSampling::MinimumDistance`, the resulting dense point set is
+    /// blue-noise-thinned to match the density contract of the other sampling modes.
+    /// `accept` carves out regions exactly as described on `sample_triangles`.
+    pub fn sample_sdf<F>(
+        mut self,
+        sdf: F,
+        bounds_min: Vec3,
+        bounds_max: Vec3,
+        resolution: [usize; 3],
+        prototype_surfel_data: &D,
+        accept: Option<&dyn Fn(&V) -> bool>,
+    ) -> Self
+    where
+        F: Fn(Vec3) -> f32,
+        V: FromPositionNormal,
+        D: Clone,
+    {
+        let dense = surface_nets(&sdf, bounds_min, bounds_max, resolution);
+
+        let points = match self.sampling {
+            SurfelSampling::MinimumDistance(min_dist) => thin_by_minimum_distance(dense, min_dist),
+            _ => dense,
+        };
+
+        let raw: Vec<V> = points
+            .into_iter()
+            .map(|(position, normal)| V::from_position_normal(position, normal))
+            .collect();
+
+        self.extend_accepted(raw, accept, prototype_surfel_data);
 
         self
     }
+
+    /// Filters `raw` through `accept` (if given), wraps the survivors as surfels with a
+    /// clone of `prototype_surfel_data` each, and adds them to `self.samples`.
+    fn extend_accepted(
+        &mut self,
+        raw: Vec<V>,
+        accept: Option<&dyn Fn(&V) -> bool>,
+        prototype_surfel_data: &D,
+    ) where
+        D: Clone,
+    {
+        let sampled = raw
+            .into_iter()
+            .filter(|v| accept.map_or(true, |accept| accept(v)))
+            .map(|v| Surfel::new(v, prototype_surfel_data.clone()));
+
+        self.samples.extend(sampled);
+    }
+}
+
+/// Default candidate pool size used to approximate `SurfelSampling::MinimumDistance`
+/// for analytic shapes via best-candidate search.
+const DEFAULT_CANDIDATES_PER_SAMPLE: usize = 30;
+
+/// Draws a single surfel vertex from a shape's boundary.
+fn draw_shape_sample<B, V, R>(shape: &B, rng: &mut R) -> V
+where
+    B: BoundarySample,
+    V: FromPositionNormal,
+    R: Rng,
+{
+    let (position, normal) = shape.sample_boundary(rng);
+    V::from_position_normal(position, normal)
+}
+
+/// Approximates the number of samples a dart-throwing pass would produce at `min_dist`
+/// spacing, assuming each sample occupies a disk of radius `min_dist / 2` on the boundary.
+fn shape_best_candidate_count<B: BoundarySample>(shape: &B, min_dist: f32) -> usize {
+    let disk_area = std::f32::consts::PI * (min_dist * 0.5) * (min_dist * 0.5);
+
+    ((shape.area() / disk_area).round() as usize).max(1)
+}
+
+/// Area-weighted Monte Carlo sampling of a shape's boundary, producing roughly
+/// `shape.area() * density` samples in expectation.
+fn sample_shape_per_sqr_unit<B, V>(shape: &B, density: f32) -> Vec<V>
+where
+    B: BoundarySample,
+    V: FromPositionNormal,
+{
+    let mut rng = thread_rng();
+    let expected_count = shape.area() * density;
+
+    let mut count = expected_count.floor() as usize;
+    if rng.gen::<f32>() < expected_count.fract() {
+        count += 1;
+    }
+
+    (0..count).map(|_| draw_shape_sample(shape, &mut rng)).collect()
+}
+
+/// Area-weighted Monte Carlo sampling of the given triangles, producing roughly
+/// `area * density` samples per triangle in expectation.
+fn sample_triangles_per_sqr_unit<I, T, V>(triangles: I, density: f32) -> Vec<V>
+where
+    T: InterpolateVertex<Vertex = V> + FromVertices<Vertex = V>,
+    V: Position,
+    I: IntoIterator<Item = T>,
+{
+    let mut rng = thread_rng();
+
+    triangles
+        .into_iter()
+        .flat_map(|triangle| {
+            let area = triangle_area(&triangle);
+            let expected_count = area * density;
+
+            let mut count = expected_count.floor() as usize;
+            if rng.gen::<f32>() < expected_count.fract() {
+                count += 1;
+            }
+
+            (0..count)
+                .map(|_| triangle.interpolate(random_barycentric(&mut rng)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Draws a uniformly distributed barycentric coordinate within a triangle by
+/// folding the unit square onto it.
+fn random_barycentric<R: Rng>(rng: &mut R) -> Vec3 {
+    let (mut u, mut v): (f32, f32) = (rng.gen(), rng.gen());
+
+    if u + v > 1.0 {
+        u = 1.0 - u;
+        v = 1.0 - v;
+    }
+
+    Vec3::new(1.0 - u - v, u, v)
+}
+
+/// Computes the world-space area of a triangle by interpolating its three corners.
+fn triangle_area<T, V>(triangle: &T) -> f32
+where
+    T: InterpolateVertex<Vertex = V>,
+    V: Position,
+{
+    let a = triangle.interpolate(Vec3::new(1.0, 0.0, 0.0)).position();
+    let b = triangle.interpolate(Vec3::new(0.0, 1.0, 0.0)).position();
+    let c = triangle.interpolate(Vec3::new(0.0, 0.0, 1.0)).position();
+
+    (b - a).cross(c - a).magnitude() * 0.5
+}
+
+/// Triangles bundled with their cumulative world-space areas, allowing samples to be
+/// drawn with probability proportional to the area of the triangle they land on.
+struct WeightedTriangles<T> {
+    triangles: Vec<T>,
+    cumulative_areas: Vec<f32>,
+    total_area: f32,
+}
+
+impl<T, V> WeightedTriangles<T>
+where
+    T: InterpolateVertex<Vertex = V>,
+    V: Position,
+{
+    fn new(triangles: Vec<T>) -> Self {
+        let mut total_area = 0.0;
+        let cumulative_areas = triangles
+            .iter()
+            .map(|triangle| {
+                total_area += triangle_area(triangle);
+                total_area
+            })
+            .collect();
+
+        WeightedTriangles {
+            triangles,
+            cumulative_areas,
+            total_area,
+        }
+    }
+
+    /// Draws a single area-weighted random sample from the triangle set.
+    fn sample<R: Rng>(&self, rng: &mut R) -> V {
+        let target = rng.gen::<f32>() * self.total_area;
+        let idx = match self
+            .cumulative_areas
+            .binary_search_by(|area| area.partial_cmp(&target).unwrap())
+        {
+            Ok(idx) | Err(idx) => idx.min(self.triangles.len() - 1),
+        };
+
+        self.triangles[idx].interpolate(random_barycentric(rng))
+    }
+}
+
+/// Adds the position of `vertex` to the spatial index under the given sample index.
+fn index_vertex<V: Position>(tree: &mut KdTree<f64, usize, [f64; 3]>, vertex: &V, idx: usize) {
+    let p = vertex.position();
+    tree.add([p.x as f64, p.y as f64, p.z as f64], idx).unwrap();
+}
+
+/// Mitchell's best-candidate sampling: seeds the result with one area-weighted random
+/// sample, then repeatedly keeps whichever of `candidates_per_sample` fresh area-weighted
+/// candidates is farthest from every sample selected so far, until `count` is reached.
+///
+/// If `accept` is given, candidates failing it are discarded before the best-of comparison,
+/// so the returned set still has exactly `count` accepted samples, unless `accept` rejects
+/// so much that `MAX_REJECTED_DRAWS` is exhausted; then fewer samples are returned.
+fn sample_triangles_best_candidate<I, T, V>(
+    triangles: I,
+    count: usize,
+    candidates_per_sample: usize,
+    accept: Option<&dyn Fn(&V) -> bool>,
+) -> Vec<V>
+where
+    T: InterpolateVertex<Vertex = V> + FromVertices<Vertex = V>,
+    V: Position,
+    I: IntoIterator<Item = T>,
+{
+    let weighted = WeightedTriangles::new(triangles.into_iter().collect());
+
+    if weighted.total_area <= 0.0 {
+        return Vec::new();
+    }
+
+    select_best_candidates(count, candidates_per_sample, accept, |rng| weighted.sample(rng))
+}
+
+/// Safety bound on rejected draws tolerated while looking for accepted candidates, so an
+/// `accept` predicate that matches nothing (or next to nothing) makes sampling give up
+/// rather than loop forever.
+const MAX_REJECTED_DRAWS: usize = 10_000;
+
+/// Draws from `draw` until `count` candidates have passed `is_accepted` or
+/// `MAX_REJECTED_DRAWS` draws have failed it, whichever comes first. The returned
+/// `Vec` has fewer than `count` elements only once the rejection budget has been exhausted.
+fn draw_accepted<V, F>(
+    count: usize,
+    rng: &mut rand::ThreadRng,
+    draw: &mut F,
+    is_accepted: &dyn Fn(&V) -> bool,
+) -> Vec<V>
+where
+    F: FnMut(&mut rand::ThreadRng) -> V,
+{
+    let mut accepted = Vec::with_capacity(count);
+    let mut rejected = 0;
+
+    while accepted.len() < count && rejected < MAX_REJECTED_DRAWS {
+        let candidate = draw(rng);
+
+        if is_accepted(&candidate) {
+            accepted.push(candidate);
+        } else {
+            rejected += 1;
+        }
+    }
+
+    accepted
+}
+
+/// Core of Mitchell's best-candidate algorithm, parameterized over how a single fresh
+/// candidate is drawn: seeds the result with one `draw`n sample, then repeatedly keeps
+/// whichever of `candidates_per_sample` fresh candidates is farthest from every sample
+/// selected so far, until `count` is reached.
+///
+/// If `accept` is given, candidates are filtered through it before being considered for
+/// selection; rejected candidates are discarded and redrawn so the function still returns
+/// exactly `count` samples, all satisfying `accept` — unless `accept` rejects so much of
+/// the draw stream that `MAX_REJECTED_DRAWS` is exhausted while looking for the seed or a
+/// round's candidates, in which case selection gives up early and returns fewer than
+/// `count` samples (possibly none) rather than looping forever.
+fn select_best_candidates<V, F>(
+    count: usize,
+    candidates_per_sample: usize,
+    accept: Option<&dyn Fn(&V) -> bool>,
+    mut draw: F,
+) -> Vec<V>
+where
+    V: Position,
+    F: FnMut(&mut rand::ThreadRng) -> V,
+{
+    if count == 0 || candidates_per_sample == 0 {
+        return Vec::new();
+    }
+
+    let is_accepted = |v: &V| accept.map_or(true, |accept| accept(v));
+
+    let mut rng = thread_rng();
+    let mut selected = Vec::with_capacity(count);
+    let mut spatial_idx = KdTree::new(3);
+
+    let seed = match draw_accepted(1, &mut rng, &mut draw, &is_accepted).pop() {
+        Some(seed) => seed,
+        None => return Vec::new(),
+    };
+    index_vertex(&mut spatial_idx, &seed, selected.len());
+    selected.push(seed);
+
+    while selected.len() < count {
+        let candidates = draw_accepted(candidates_per_sample, &mut rng, &mut draw, &is_accepted);
+
+        let best_candidate = candidates.into_iter().max_by(|a, b| {
+            let distance_to = |v: &V| {
+                let p = v.position();
+                spatial_idx
+                    .nearest(&[p.x as f64, p.y as f64, p.z as f64], 1, &squared_euclidean)
+                    .unwrap()[0]
+                    .0
+            };
+
+            distance_to(a).partial_cmp(&distance_to(b)).unwrap()
+        });
+
+        match best_candidate {
+            Some(candidate) => {
+                index_vertex(&mut spatial_idx, &candidate, selected.len());
+                selected.push(candidate);
+            }
+            None => break,
+        }
+    }
+
+    selected
 }
 
 impl<S: Position> SurfaceBuilder<S> {
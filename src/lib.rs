@@ -1,17 +1,21 @@
 //! Manages collections of points that represent a surface.
 //!
 //! Provides functionality for building and searching surfaces, as well as
-//! functionality to sample points on triangle meshes.
+//! functionality to sample points on triangle meshes and analytic primitive shapes.
 
 extern crate aitios_geom as geom;
 extern crate aitios_sampling as sampling;
 extern crate aitios_scene as scene;
 extern crate nearest_kdtree;
+extern crate rand;
 
 mod builder;
+mod sdf;
+mod shape;
 mod surface;
 mod surfel;
 
 pub use builder::{SurfaceBuilder, SurfelSampling};
+pub use shape::{BoundarySample, Cuboid, Cylinder, FromPositionNormal, Sphere, Torus};
 pub use surface::Surface;
 pub use surfel::Surfel;
@@ -0,0 +1,130 @@
+use geom::Vec3;
+use nearest_kdtree::distance::squared_euclidean;
+use nearest_kdtree::KdTree;
+
+/// Corner index pairs for the 12 edges of a unit cube, in the same corner
+/// ordering as the `corners` array built in `surface_nets`.
+const CUBE_EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Extracts the zero isosurface of `sdf` over the grid spanned by `bounds_min`..`bounds_max`
+/// with `resolution` cells per axis, using Naive Surface Nets. Emits one dual vertex (with
+/// an estimated normal) per grid cube whose corners straddle the surface.
+pub(crate) fn surface_nets<F>(
+    sdf: &F,
+    bounds_min: Vec3,
+    bounds_max: Vec3,
+    resolution: [usize; 3],
+) -> Vec<(Vec3, Vec3)>
+where
+    F: Fn(Vec3) -> f32,
+{
+    let [cells_x, cells_y, cells_z] = resolution;
+    let cell_size = Vec3::new(
+        (bounds_max.x - bounds_min.x) / cells_x as f32,
+        (bounds_max.y - bounds_min.y) / cells_y as f32,
+        (bounds_max.z - bounds_min.z) / cells_z as f32,
+    );
+
+    let grid_point = |i: usize, j: usize, k: usize| {
+        bounds_min + Vec3::new(i as f32 * cell_size.x, j as f32 * cell_size.y, k as f32 * cell_size.z)
+    };
+
+    let mut vertices = Vec::new();
+
+    for i in 0..cells_x {
+        for j in 0..cells_y {
+            for k in 0..cells_z {
+                let corners = [
+                    grid_point(i, j, k),
+                    grid_point(i + 1, j, k),
+                    grid_point(i + 1, j + 1, k),
+                    grid_point(i, j + 1, k),
+                    grid_point(i, j, k + 1),
+                    grid_point(i + 1, j, k + 1),
+                    grid_point(i + 1, j + 1, k + 1),
+                    grid_point(i, j + 1, k + 1),
+                ];
+                let values: Vec<f32> = corners.iter().map(|&p| sdf(p)).collect();
+
+                let crossings: Vec<Vec3> = CUBE_EDGES
+                    .iter()
+                    .filter_map(|&(a, b)| {
+                        let (value_a, value_b) = (values[a], values[b]);
+
+                        if (value_a < 0.0) == (value_b < 0.0) {
+                            return None;
+                        }
+
+                        let t = value_a / (value_a - value_b);
+                        Some(corners[a] + (corners[b] - corners[a]) * t)
+                    })
+                    .collect();
+
+                if crossings.is_empty() {
+                    continue;
+                }
+
+                let count = crossings.len() as f32;
+                let sum = crossings
+                    .iter()
+                    .fold(Vec3::new(0.0, 0.0, 0.0), |acc, &p| acc + p);
+                let position = sum * (1.0 / count);
+                let normal = gradient_normal(sdf, position, cell_size);
+
+                vertices.push((position, normal));
+            }
+        }
+    }
+
+    vertices
+}
+
+/// Estimates the SDF gradient at `p` via central differencing, using a step half the
+/// size of the smallest grid cell dimension.
+fn gradient_normal<F: Fn(Vec3) -> f32>(sdf: &F, p: Vec3, cell_size: Vec3) -> Vec3 {
+    let h = cell_size.x.min(cell_size.y).min(cell_size.z) * 0.5;
+
+    let dx = sdf(p + Vec3::new(h, 0.0, 0.0)) - sdf(p - Vec3::new(h, 0.0, 0.0));
+    let dy = sdf(p + Vec3::new(0.0, h, 0.0)) - sdf(p - Vec3::new(0.0, h, 0.0));
+    let dz = sdf(p + Vec3::new(0.0, 0.0, h)) - sdf(p - Vec3::new(0.0, 0.0, h));
+
+    Vec3::new(dx, dy, dz).normalize()
+}
+
+/// Greedily thins a dense point set so that no two points remain closer than `min_dist`,
+/// matching the density contract of `SurfelSampling::MinimumDistance`.
+pub(crate) fn thin_by_minimum_distance(points: Vec<(Vec3, Vec3)>, min_dist: f32) -> Vec<(Vec3, Vec3)> {
+    let mut accepted = Vec::new();
+    let mut spatial_idx: KdTree<f64, usize, [f64; 3]> = KdTree::new(3);
+    let min_dist_sqr = (min_dist as f64) * (min_dist as f64);
+
+    for (position, normal) in points {
+        let p = [position.x as f64, position.y as f64, position.z as f64];
+
+        let too_close = spatial_idx
+            .nearest(&p, 1, &squared_euclidean)
+            .ok()
+            .and_then(|neighbors| neighbors.first().map(|&(d, _)| d < min_dist_sqr))
+            .unwrap_or(false);
+
+        if !too_close {
+            spatial_idx.add(p, accepted.len()).unwrap();
+            accepted.push((position, normal));
+        }
+    }
+
+    accepted
+}
@@ -0,0 +1,182 @@
+use geom::Vec3;
+use rand::Rng;
+use std::f32::consts::PI;
+
+/// Constructs a vertex directly from a world-space position and normal. Implement this
+/// for vertex types used with `SurfaceBuilder::sample_shape`, where surfels are placed
+/// analytically and there is no triangle to interpolate from.
+pub trait FromPositionNormal {
+    fn from_position_normal(position: Vec3, normal: Vec3) -> Self;
+}
+
+/// An analytic primitive whose boundary can be sampled directly, without first
+/// tessellating it into a triangle mesh.
+pub trait BoundarySample {
+    /// World-space surface area of the shape's boundary.
+    fn area(&self) -> f32;
+
+    /// Draws one uniformly distributed point on the boundary, together with the
+    /// outward-facing surface normal at that point.
+    fn sample_boundary<R: Rng>(&self, rng: &mut R) -> (Vec3, Vec3);
+}
+
+/// A sphere, sampled uniformly via Marsaglia's method: normalizing a vector of three
+/// independent standard-normal samples yields a uniformly distributed direction.
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl BoundarySample for Sphere {
+    fn area(&self) -> f32 {
+        4.0 * PI * self.radius * self.radius
+    }
+
+    fn sample_boundary<R: Rng>(&self, rng: &mut R) -> (Vec3, Vec3) {
+        let normal = Vec3::new(
+            standard_normal(rng),
+            standard_normal(rng),
+            standard_normal(rng),
+        )
+        .normalize();
+
+        (self.center + normal * self.radius, normal)
+    }
+}
+
+/// An axis-aligned cuboid, sampled by picking one of its six faces with probability
+/// proportional to its area, then a uniform point on that face.
+pub struct Cuboid {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl BoundarySample for Cuboid {
+    fn area(&self) -> f32 {
+        let e = self.half_extents;
+        8.0 * (e.x * e.y + e.y * e.z + e.z * e.x)
+    }
+
+    fn sample_boundary<R: Rng>(&self, rng: &mut R) -> (Vec3, Vec3) {
+        let e = self.half_extents;
+        let face_areas = [e.y * e.z, e.x * e.z, e.x * e.y];
+        let total_face_area: f32 = face_areas.iter().sum();
+
+        let mut target = rng.gen::<f32>() * total_face_area;
+        let mut axis = face_areas.len() - 1;
+        for (i, &face_area) in face_areas.iter().enumerate() {
+            if target < face_area {
+                axis = i;
+                break;
+            }
+            target -= face_area;
+        }
+
+        let sign = if rng.gen::<bool>() { 1.0 } else { -1.0 };
+        let u = rng.gen::<f32>() * 2.0 - 1.0;
+        let v = rng.gen::<f32>() * 2.0 - 1.0;
+
+        let (local, normal) = match axis {
+            0 => (Vec3::new(sign, u, v), Vec3::new(sign, 0.0, 0.0)),
+            1 => (Vec3::new(u, sign, v), Vec3::new(0.0, sign, 0.0)),
+            _ => (Vec3::new(u, v, sign), Vec3::new(0.0, 0.0, sign)),
+        };
+
+        let position = self.center + Vec3::new(local.x * e.x, local.y * e.y, local.z * e.z);
+
+        (position, normal)
+    }
+}
+
+/// A cylinder whose axis runs along Y, sampled by splitting probability between the
+/// lateral surface (uniform in angle and height) and the two end caps in proportion
+/// to their areas.
+pub struct Cylinder {
+    pub center: Vec3,
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl BoundarySample for Cylinder {
+    fn area(&self) -> f32 {
+        let lateral_area = 2.0 * PI * self.radius * self.height;
+        let cap_area = 2.0 * PI * self.radius * self.radius;
+
+        lateral_area + cap_area
+    }
+
+    fn sample_boundary<R: Rng>(&self, rng: &mut R) -> (Vec3, Vec3) {
+        let lateral_area = 2.0 * PI * self.radius * self.height;
+        let cap_area = 2.0 * PI * self.radius * self.radius;
+
+        let angle = rng.gen::<f32>() * 2.0 * PI;
+        let (sin, cos) = angle.sin_cos();
+
+        if rng.gen::<f32>() * (lateral_area + cap_area) < lateral_area {
+            let y = (rng.gen::<f32>() - 0.5) * self.height;
+            let position = self.center + Vec3::new(cos * self.radius, y, sin * self.radius);
+            let normal = Vec3::new(cos, 0.0, sin);
+
+            (position, normal)
+        } else {
+            let r = self.radius * rng.gen::<f32>().sqrt();
+            let top = rng.gen::<bool>();
+            let y = if top {
+                self.height * 0.5
+            } else {
+                -self.height * 0.5
+            };
+            let normal = Vec3::new(0.0, if top { 1.0 } else { -1.0 }, 0.0);
+            let position = self.center + Vec3::new(cos * r, y, sin * r);
+
+            (position, normal)
+        }
+    }
+}
+
+/// A torus whose axis runs along Y, with `major_radius` the distance from the torus
+/// axis to the center of the tube and `minor_radius` the radius of the tube itself.
+pub struct Torus {
+    pub center: Vec3,
+    pub major_radius: f32,
+    pub minor_radius: f32,
+}
+
+impl BoundarySample for Torus {
+    fn area(&self) -> f32 {
+        4.0 * PI * PI * self.major_radius * self.minor_radius
+    }
+
+    fn sample_boundary<R: Rng>(&self, rng: &mut R) -> (Vec3, Vec3) {
+        // theta sweeps the tube's circumference, which varies with theta, so the angle
+        // is drawn by rejection: accept with probability proportional to the circumference
+        // it would sweep out, correcting the otherwise uneven density near the inner rim.
+        let theta = loop {
+            let theta = rng.gen::<f32>() * 2.0 * PI;
+            let acceptance = (self.major_radius + self.minor_radius * theta.cos())
+                / (self.major_radius + self.minor_radius);
+
+            if rng.gen::<f32>() < acceptance {
+                break theta;
+            }
+        };
+
+        let phi = rng.gen::<f32>() * 2.0 * PI;
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        let normal = Vec3::new(cos_theta * cos_phi, sin_theta, cos_theta * sin_phi);
+        let tube_center = Vec3::new(self.major_radius * cos_phi, 0.0, self.major_radius * sin_phi);
+        let position = self.center + tube_center + normal * self.minor_radius;
+
+        (position, normal)
+    }
+}
+
+/// Draws a sample from the standard normal distribution via the Box-Muller transform.
+fn standard_normal<R: Rng>(rng: &mut R) -> f32 {
+    let u1 = rng.gen::<f32>().max(std::f32::EPSILON);
+    let u2 = rng.gen::<f32>();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
@@ -0,0 +1,68 @@
+use geom::{Position, Vec3};
+use nearest_kdtree::distance::squared_euclidean;
+use nearest_kdtree::KdTree;
+use std::io;
+use std::io::Write;
+
+/// A cloud of surfels distributed over one or more surfaces, together with
+/// a spatial index that allows efficient nearest-neighbor queries.
+pub struct Surface<S: Position> {
+    /// The surfels that make up this surface.
+    pub samples: Vec<S>,
+    /// Spatial index over `samples`, mapping positions to indices into `samples`.
+    pub(crate) spatial_idx: KdTree<f64, usize, [f64; 3]>,
+}
+
+impl<S: Position> Surface<S> {
+    /// Writes the positions of all samples as vertices to the given sink in
+    /// Wavefront OBJ format, e.g. for inspecting the resulting point cloud in Blender.
+    pub fn dump<W: Write>(&self, sink: &mut W) -> io::Result<()> {
+        for sample in &self.samples {
+            let p = sample.position();
+            writeln!(sink, "v {} {} {}", p.x, p.y, p.z)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the sample closest to `p`, or `None` if the surface has no samples.
+    pub fn nearest(&self, p: Vec3) -> Option<&S> {
+        self.spatial_idx
+            .nearest(&to_point(p), 1, &squared_euclidean)
+            .ok()
+            .and_then(|neighbors| neighbors.first().map(|&(_, &idx)| &self.samples[idx]))
+    }
+
+    /// Returns up to `n` samples closest to `p`, ordered by increasing distance.
+    pub fn nearest_n(&self, p: Vec3, n: usize) -> Vec<&S> {
+        self.spatial_idx
+            .nearest(&to_point(p), n, &squared_euclidean)
+            .map(|neighbors| {
+                neighbors
+                    .into_iter()
+                    .map(|(_, &idx)| &self.samples[idx])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns every sample within euclidean distance `r` of `p`.
+    pub fn within_radius(&self, p: Vec3, r: f32) -> Vec<&S> {
+        let r_sqr = (r as f64) * (r as f64);
+
+        self.spatial_idx
+            .within(&to_point(p), r_sqr, &squared_euclidean)
+            .map(|neighbors| {
+                neighbors
+                    .into_iter()
+                    .map(|(_, &idx)| &self.samples[idx])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Converts a position into the `[f64; 3]` point representation expected by `spatial_idx`.
+fn to_point(p: Vec3) -> [f64; 3] {
+    [p.x as f64, p.y as f64, p.z as f64]
+}
@@ -1,18 +1,39 @@
 extern crate aitios_asset as asset;
+extern crate aitios_geom as geom;
 extern crate aitios_scene as scene;
 extern crate aitios_surf as surf;
 
 use asset::obj;
+use geom::{Position, Vec3};
 use scene::Mesh;
 use std::fs::File;
 use std::path::PathBuf;
-use surf::{SurfaceBuilder, SurfelSampling};
+use surf::{FromPositionNormal, Sphere, Surface, SurfaceBuilder, SurfelSampling};
 
 #[derive(Clone)]
 struct SurfelData {
     prop: i32,
 }
 
+/// Minimal vertex type for exercising `sample_shape`/`sample_sdf`, which need a way to
+/// build a vertex straight from a world-space position and normal.
+#[derive(Clone)]
+struct ShapeVertex {
+    position: Vec3,
+}
+
+impl Position for ShapeVertex {
+    fn position(&self) -> Vec3 {
+        self.position
+    }
+}
+
+impl FromPositionNormal for ShapeVertex {
+    fn from_position_normal(position: Vec3, _normal: Vec3) -> Self {
+        ShapeVertex { position }
+    }
+}
+
 #[test]
 fn test_torus() {
     let torus = obj::load("tests/torus.obj").expect("Could not load test geometry");
@@ -23,7 +44,7 @@ fn test_torus() {
 
     let surface = SurfaceBuilder::new()
         .sampling(SurfelSampling::MinimumDistance(0.1))
-        .sample_triangles(torus_triangles, &prototype_surfel_data)
+        .sample_triangles(torus_triangles, &prototype_surfel_data, None)
         .build();
 
     assert_eq!(prototype_surfel_data.prop, surface.samples[0].data().prop);
@@ -38,3 +59,205 @@ fn test_torus() {
     // And finally dump the geometry to the OBJ at "circle_vertices.obj"
     surface.dump(sink).unwrap();
 }
+
+#[test]
+fn test_per_sqr_unit_respects_density() {
+    let torus = obj::load("tests/torus.obj").expect("Could not load test geometry");
+    let prototype_surfel_data = SurfelData { prop: 3 };
+
+    // Zero density must deterministically yield zero samples, regardless of mesh area.
+    let empty = SurfaceBuilder::new()
+        .sampling(SurfelSampling::PerSqrUnit(0.0))
+        .sample_triangles(
+            torus.iter().flat_map(|e| e.mesh.triangles()),
+            &prototype_surfel_data,
+            None,
+        )
+        .build();
+
+    assert_eq!(0, empty.samples.len());
+
+    // A high density should produce plenty of samples over the torus surface.
+    let dense = SurfaceBuilder::new()
+        .sampling(SurfelSampling::PerSqrUnit(1000.0))
+        .sample_triangles(
+            torus.iter().flat_map(|e| e.mesh.triangles()),
+            &prototype_surfel_data,
+            None,
+        )
+        .build();
+
+    assert!(dense.samples.len() > 100);
+}
+
+#[test]
+fn test_best_candidate_yields_exact_count() {
+    let torus = obj::load("tests/torus.obj").expect("Could not load test geometry");
+    let prototype_surfel_data = SurfelData { prop: 5 };
+
+    let surface = SurfaceBuilder::new()
+        .sampling(SurfelSampling::BestCandidate {
+            count: 40,
+            candidates_per_sample: 10,
+        })
+        .sample_triangles(
+            torus.iter().flat_map(|e| e.mesh.triangles()),
+            &prototype_surfel_data,
+            None,
+        )
+        .build();
+
+    assert_eq!(40, surface.samples.len());
+
+    // A candidate pool of zero must yield no surfels rather than hang, per the
+    // documented precondition on `candidates_per_sample`.
+    let empty = SurfaceBuilder::new()
+        .sampling(SurfelSampling::BestCandidate {
+            count: 40,
+            candidates_per_sample: 0,
+        })
+        .sample_triangles(
+            torus.iter().flat_map(|e| e.mesh.triangles()),
+            &prototype_surfel_data,
+            None,
+        )
+        .build();
+
+    assert_eq!(0, empty.samples.len());
+}
+
+#[test]
+fn test_surface_nearest_queries() {
+    let points = vec![
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(10.0, 0.0, 0.0),
+    ];
+
+    let surface: Surface<Vec3> = SurfaceBuilder::new().add_samples(points).build();
+
+    let nearest = surface.nearest(Vec3::new(0.2, 0.0, 0.0)).unwrap();
+    assert_eq!(0.0, nearest.x);
+
+    let nearest_two = surface.nearest_n(Vec3::new(0.0, 0.0, 0.0), 2);
+    assert_eq!(2, nearest_two.len());
+    assert_eq!(0.0, nearest_two[0].x);
+    assert_eq!(1.0, nearest_two[1].x);
+
+    let within = surface.within_radius(Vec3::new(0.0, 0.0, 0.0), 2.5);
+    assert_eq!(3, within.len());
+}
+
+#[test]
+fn test_sample_shape_sphere_stays_on_boundary() {
+    let sphere = Sphere {
+        center: Vec3::new(1.0, 2.0, 3.0),
+        radius: 2.0,
+    };
+    let prototype_surfel_data = SurfelData { prop: 7 };
+
+    let surface: Surface<surf::Surfel<ShapeVertex, SurfelData>> = SurfaceBuilder::new()
+        .sampling(SurfelSampling::BestCandidate {
+            count: 50,
+            candidates_per_sample: 10,
+        })
+        .sample_shape(&sphere, &prototype_surfel_data, None)
+        .build();
+
+    assert_eq!(50, surface.samples.len());
+
+    for surfel in &surface.samples {
+        let p = surfel.vertex().position();
+        let offset = p - sphere.center;
+        let distance = (offset.x * offset.x + offset.y * offset.y + offset.z * offset.z).sqrt();
+
+        assert!((distance - sphere.radius).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn test_sample_sdf_sphere_stays_near_boundary() {
+    let radius = 2.0_f32;
+    let sdf = |p: Vec3| (p.x * p.x + p.y * p.y + p.z * p.z).sqrt() - radius;
+    let prototype_surfel_data = SurfelData { prop: 9 };
+
+    let resolution = [24, 24, 24];
+    let surface: Surface<surf::Surfel<ShapeVertex, SurfelData>> = SurfaceBuilder::new()
+        .sample_sdf(
+            sdf,
+            Vec3::new(-3.0, -3.0, -3.0),
+            Vec3::new(3.0, 3.0, 3.0),
+            resolution,
+            &prototype_surfel_data,
+            None,
+        )
+        .build();
+
+    assert!(!surface.samples.is_empty());
+
+    // Dual vertices land within roughly a cell of the true surface.
+    let cell_size = 6.0 / resolution[0] as f32;
+
+    for surfel in &surface.samples {
+        let p = surfel.vertex().position();
+        let distance = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+
+        assert!((distance - radius).abs() < cell_size);
+    }
+}
+
+#[test]
+fn test_accept_carves_out_region() {
+    let sphere = Sphere {
+        center: Vec3::new(0.0, 0.0, 0.0),
+        radius: 2.0,
+    };
+    let prototype_surfel_data = SurfelData { prop: 11 };
+
+    let upper_hemisphere_only = |v: &ShapeVertex| v.position.y >= 0.0;
+
+    let surface: Surface<surf::Surfel<ShapeVertex, SurfelData>> = SurfaceBuilder::new()
+        .sampling(SurfelSampling::PerSqrUnit(50.0))
+        .sample_shape(
+            &sphere,
+            &prototype_surfel_data,
+            Some(&upper_hemisphere_only),
+        )
+        .build();
+
+    assert!(!surface.samples.is_empty());
+
+    for surfel in &surface.samples {
+        assert!(surfel.vertex().position().y >= 0.0);
+    }
+}
+
+#[test]
+fn test_best_candidate_with_accept_still_yields_exact_count() {
+    let sphere = Sphere {
+        center: Vec3::new(0.0, 0.0, 0.0),
+        radius: 2.0,
+    };
+    let prototype_surfel_data = SurfelData { prop: 13 };
+
+    let upper_hemisphere_only = |v: &ShapeVertex| v.position.y >= 0.0;
+
+    let surface: Surface<surf::Surfel<ShapeVertex, SurfelData>> = SurfaceBuilder::new()
+        .sampling(SurfelSampling::BestCandidate {
+            count: 30,
+            candidates_per_sample: 10,
+        })
+        .sample_shape(
+            &sphere,
+            &prototype_surfel_data,
+            Some(&upper_hemisphere_only),
+        )
+        .build();
+
+    assert_eq!(30, surface.samples.len());
+
+    for surfel in &surface.samples {
+        assert!(surfel.vertex().position().y >= 0.0);
+    }
+}